@@ -0,0 +1,120 @@
+use pinocchio::{AccountView, Address, ProgramResult};
+use solana_program_error::ProgramError;
+use solana_program_log::log;
+
+use crate::state::ShieldedPoolState;
+
+/// Sets the pool's circuit breaker: a nonzero data byte pauses the pool, a
+/// zero byte unpauses it. While paused, deposits and withdrawals are
+/// rejected outright.
+pub fn process_set_paused(accounts: &[AccountView], data: &[u8]) -> ProgramResult {
+    // Accounts: [authority, state]
+    let [authority, state_account] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !state_account.is_writable() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if data.len() != 1 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let paused = data[0] != 0;
+
+    if state_account.address() != &Address::find_program_address(&[b"pool_state"], &crate::ID).0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !state_account.owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let mut state_data = state_account.try_borrow_mut()?;
+    let state: &mut ShieldedPoolState =
+        bytemuck::from_bytes_mut(&mut state_data[..ShieldedPoolState::LEN]);
+
+    if !state.is_initialized() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if state.authority != *authority.address() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    state.paused = paused as u8;
+
+    log("Pool pause state updated");
+    Ok(())
+}
+
+/// Emergency recovery path: sweeps the full vault balance to an
+/// authority-specified destination, e.g. if a proof-system bug or a
+/// compromised root is discovered. Resets `total_deposited` to 0 so it keeps
+/// matching the (now empty) vault; the nullifier set and Merkle tree are
+/// left untouched, so already-spent notes stay spent and the tree's history
+/// is preserved for investigation, even though the funds behind it are gone.
+pub fn process_clawback(accounts: &[AccountView], _data: &[u8]) -> ProgramResult {
+    // Accounts: [authority, state, vault, destination]
+    let [authority, state_account, vault, destination] = accounts else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !state_account.is_writable() || !vault.is_writable() || !destination.is_writable() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if state_account.address() != &Address::find_program_address(&[b"pool_state"], &crate::ID).0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !state_account.owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    // Same vault PDA ownership checks process_deposit already performs.
+    if vault.address() != &Address::find_program_address(&[b"vault"], &crate::ID).0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !vault.owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let mut state_data = state_account.try_borrow_mut()?;
+    let state: &mut ShieldedPoolState =
+        bytemuck::from_bytes_mut(&mut state_data[..ShieldedPoolState::LEN]);
+
+    if !state.is_initialized() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if state.authority != *authority.address() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // The vault is swept to zero, so the accounting total must follow it or the
+    // total_deposited <= vault balance invariant breaks the moment this instruction runs.
+    state.total_deposited = 0;
+    drop(state_data);
+
+    // Vault is owned by this program, so lamports can be debited directly without a System
+    // Program CPI; crediting the destination never requires ownership.
+    let amount = vault.lamports();
+    *vault.try_borrow_mut_lamports()? = 0;
+    *destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    log("Vault clawed back to authority destination");
+    Ok(())
+}
@@ -19,8 +19,8 @@ pub fn process_deposit(accounts: &[AccountView], data: &[u8]) -> ProgramResult {
         return Err(ProgramError::InvalidAccountData);
     }
 
-    // Data layout: [amount: u64] [commitment: [u8; 32]] [new_root: [u8; 32]]
-    if data.len() != 72 {
+    // Data layout: [amount: u64] [commitment: [u8; 32]]
+    if data.len() != 40 {
         return Err(ProgramError::InvalidInstructionData);
     }
     let amount = u64::from_le_bytes(data[0..8].try_into().map_err(|_| {
@@ -29,9 +29,6 @@ pub fn process_deposit(accounts: &[AccountView], data: &[u8]) -> ProgramResult {
     let commitment: [u8; 32] = data[8..40]
         .try_into()
         .map_err(|_| ProgramError::InvalidInstructionData)?;
-    let new_root: [u8; 32] = data[40..72]
-        .try_into()
-        .map_err(|_| ProgramError::InvalidInstructionData)?;
 
     // C-03 mitigation: Prevent zero-amount deposits that allow root injection without economic cost.
     // An attacker can inject an arbitrary Merkle root via a zero-lamport deposit, then withdraw
@@ -41,17 +38,8 @@ pub fn process_deposit(accounts: &[AccountView], data: &[u8]) -> ProgramResult {
         return Err(ProgramError::InvalidInstructionData);
     }
 
-    log("Processing Deposit");
-
-    // Transfer SOL to the vault.
-    SystemTransfer {
-        from: payer,
-        to: vault,
-        lamports: amount,
-    }
-    .invoke()?;
-
-    // Update the stored Merkle root.
+    // Validate everything before moving any lamports: a paused or malformed deposit must
+    // reject outright rather than transfer first and unwind after.
     if state_account.address() != &Address::find_program_address(&[b"pool_state"], &crate::ID).0 {
         return Err(ProgramError::InvalidAccountData);
     }
@@ -76,18 +64,33 @@ pub fn process_deposit(accounts: &[AccountView], data: &[u8]) -> ProgramResult {
         return Err(ProgramError::UninitializedAccount);
     }
 
-    // TODO(C-01/C-02): The root MUST be computed on-chain from the commitment, not accepted from
-    // instruction data. The current design allows any caller to inject an arbitrary Merkle root.
-    // Fix requires:
-    //   1. Store commitments (leaves) in an on-chain Merkle tree account
-    //   2. Insert `commitment` into the tree on deposit
-    //   3. Compute and store the new root from the tree â€” never from client data
-    //   4. Remove `new_root` from instruction data entirely
-    // Until then, the pool's root-of-trust is fundamentally broken: any deposit can overwrite the
-    // Merkle root, enabling a vault drain via crafted ZK proofs against attacker-controlled roots.
-    let _ = commitment; // Used after on-chain Merkle tree is implemented (see TODO above)
+    if state.is_paused() {
+        log("Pool is paused");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    log("Processing Deposit");
+
+    // Transfer SOL to the vault.
+    SystemTransfer {
+        from: payer,
+        to: vault,
+        lamports: amount,
+    }
+    .invoke()?;
+
+    // C-01/C-02 fix: the root is derived on-chain from the commitment, never accepted from
+    // instruction data, so a depositor can no longer inject an arbitrary root and drain the
+    // vault with a proof against an attacker-chosen tree.
+    let new_root = state.tree.insert(commitment)?;
     state.add_root(new_root);
 
+    // Mirrors the runtime's own checked_add for lamports: never silently wrap on overflow.
+    state.total_deposited = state
+        .total_deposited
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
     log("Deposit successful, root updated");
     Ok(())
 }
@@ -0,0 +1,7 @@
+pub mod admin;
+pub mod deposit;
+pub mod withdraw;
+
+pub use admin::{process_clawback, process_set_paused};
+pub use deposit::process_deposit;
+pub use withdraw::process_withdraw;
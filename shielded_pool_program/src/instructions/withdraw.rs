@@ -0,0 +1,157 @@
+use pinocchio::{
+    instruction::{Seed, Signer},
+    sysvars::{rent::Rent, Sysvar},
+    AccountView, Address, ProgramResult,
+};
+use pinocchio_system::instructions::CreateAccount;
+use solana_program_error::ProgramError;
+use solana_program_log::log;
+
+use crate::proof::verify_withdraw_proof;
+use crate::state::ShieldedPoolState;
+
+/// The nullifier account only ever needs to hold its single spent-flag byte.
+const NULLIFIER_ACCOUNT_LEN: u64 = 1;
+
+pub fn process_withdraw(accounts: &[AccountView], data: &[u8]) -> ProgramResult {
+    // Accounts: [payer, state, vault, nullifier, recipient, system_program]
+    let [payer, state_account, vault, nullifier_account, recipient, _system_program] = accounts
+    else {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    };
+
+    if !payer.is_signer() {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    if !state_account.is_writable() || !vault.is_writable() || !nullifier_account.is_writable() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Data layout: [root: [u8; 32]] [nullifier: [u8; 32]] [recipient: [u8; 32]] [amount: u64] [proof: ..]
+    if data.len() < 104 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let root: [u8; 32] = data[0..32]
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let nullifier: [u8; 32] = data[32..64]
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let recipient_key: [u8; 32] = data[64..96]
+        .try_into()
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+    let amount = u64::from_le_bytes(
+        data[96..104]
+            .try_into()
+            .map_err(|_| ProgramError::InvalidInstructionData)?,
+    );
+    let proof = &data[104..];
+
+    if recipient.address() != &recipient_key {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if state_account.address() != &Address::find_program_address(&[b"pool_state"], &crate::ID).0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !state_account.owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    if vault.address() != &Address::find_program_address(&[b"vault"], &crate::ID).0 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if !vault.owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let (nullifier_pda, nullifier_bump) =
+        Address::find_program_address(&[b"nullifier", &nullifier], &crate::ID);
+    if nullifier_account.address() != &nullifier_pda {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // A note's nullifier account doesn't exist until its first (and only legitimate) spend, so
+    // create it here rather than requiring the caller to have created it in the same transaction.
+    // Once created and owned by this program, the account is no longer system-owned, so
+    // re-spending the same note skips CreateAccount and hits the spent-flag check below.
+    if nullifier_account.owned_by(&pinocchio_system::ID) && nullifier_account.lamports() == 0 {
+        let bump_seed = [nullifier_bump];
+        let signer_seeds = [
+            Seed::from(b"nullifier"),
+            Seed::from(&nullifier),
+            Seed::from(&bump_seed),
+        ];
+        let signer = Signer::from(&signer_seeds);
+
+        CreateAccount {
+            from: payer,
+            to: nullifier_account,
+            lamports: Rent::get()?.minimum_balance(NULLIFIER_ACCOUNT_LEN as usize),
+            space: NULLIFIER_ACCOUNT_LEN,
+            owner: &crate::ID,
+        }
+        .invoke_signed(&[signer])?;
+    } else if !nullifier_account.owned_by(&crate::ID) {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let mut state_data = state_account.try_borrow_mut()?;
+    let state: &mut ShieldedPoolState =
+        bytemuck::from_bytes_mut(&mut state_data[..ShieldedPoolState::LEN]);
+
+    if !state.is_initialized() {
+        return Err(ProgramError::UninitializedAccount);
+    }
+
+    if state.is_paused() {
+        log("Pool is paused");
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // Tornado-style: the proof's root only has to fall within the retained history window, so
+    // a proof built against a slightly stale tree still verifies.
+    if !state.root_valid(&root) {
+        log("Root is outside the retained history window");
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    verify_withdraw_proof(proof, &root, &nullifier, &recipient_key, amount)?;
+
+    // The nullifier account is a single spent-flag byte. Reject before moving any funds so the
+    // same note can never be withdrawn twice.
+    let mut nullifier_data = nullifier_account.try_borrow_mut()?;
+    if nullifier_data.is_empty() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    if nullifier_data[0] != 0 {
+        log("Nullifier already spent");
+        return Err(ProgramError::AccountAlreadyInitialized);
+    }
+    nullifier_data[0] = 1;
+    drop(nullifier_data);
+
+    // Mirrors the checked_add on the deposit side: never silently wrap the accounting total.
+    state.total_deposited = state
+        .total_deposited
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    drop(state_data);
+
+    // Vault is owned by this program, so lamports can be debited directly without a System
+    // Program CPI; crediting the recipient never requires ownership.
+    *vault.try_borrow_mut_lamports()? = vault
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(ProgramError::InsufficientFunds)?;
+    *recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    log("Withdraw successful");
+    Ok(())
+}
@@ -0,0 +1,31 @@
+#![cfg_attr(not(test), no_std)]
+
+use pinocchio::{entrypoint, AccountView, Address, ProgramResult};
+use solana_program_error::ProgramError;
+
+pub mod instructions;
+pub mod merkle;
+pub mod proof;
+pub mod state;
+
+pinocchio_pubkey::declare_id!("ShPoo11111111111111111111111111111111111111");
+
+entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    _program_id: &Address,
+    accounts: &[AccountView],
+    data: &[u8],
+) -> ProgramResult {
+    let (discriminator, rest) = data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    match discriminator {
+        0 => instructions::process_deposit(accounts, rest),
+        1 => instructions::process_withdraw(accounts, rest),
+        2 => instructions::process_set_paused(accounts, rest),
+        3 => instructions::process_clawback(accounts, rest),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
@@ -0,0 +1,141 @@
+use bytemuck::{Pod, Zeroable};
+use solana_keccak_hasher::hashv;
+use solana_program_error::ProgramError;
+
+/// Depth of the on-chain incremental Merkle tree (matches the depth the ZK
+/// circuit is compiled against).
+pub const DEPTH: usize = 20;
+
+/// Maximum number of leaves (commitments) the tree can hold before it is full.
+pub const MAX_LEAVES: u64 = 1 << DEPTH;
+
+/// Value of an unfilled leaf: raw zero, not a hash. The circuit must treat
+/// an empty leaf as all-zero bytes for `zeros[0]` to match this tree.
+const EMPTY_LEAF: [u8; 32] = [0u8; 32];
+
+/// Hashes two child nodes into their parent. Single entry point so the
+/// on-chain tree and the ZK circuit agree on the exact hash used at every
+/// level.
+pub fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[left, right]).to_bytes()
+}
+
+fn build_zeros() -> [[u8; 32]; DEPTH] {
+    let mut zeros = [[0u8; 32]; DEPTH];
+    zeros[0] = EMPTY_LEAF;
+    let mut i = 0;
+    while i + 1 < DEPTH {
+        zeros[i + 1] = hash_pair(&zeros[i], &zeros[i]);
+        i += 1;
+    }
+    zeros
+}
+
+/// Fixed-depth incremental Merkle tree, insert-only. Holds just enough state
+/// to append a new leaf and derive the resulting root without ever storing
+/// the full tree.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct MerkleTree {
+    pub filled_subtrees: [[u8; 32]; DEPTH],
+    pub zeros: [[u8; 32]; DEPTH],
+    pub next_index: u64,
+}
+
+impl MerkleTree {
+    /// Sets the tree to its empty state. Must be called once, at pool
+    /// initialization, before the first `insert`.
+    pub fn init(&mut self) {
+        self.zeros = build_zeros();
+        self.filled_subtrees = self.zeros;
+        self.next_index = 0;
+    }
+
+    /// Inserts `leaf` as the next commitment and returns the resulting root.
+    /// The root is always a deterministic function of the leaves inserted so
+    /// far; callers can never supply it directly.
+    pub fn insert(&mut self, leaf: [u8; 32]) -> Result<[u8; 32], ProgramError> {
+        if self.next_index >= MAX_LEAVES {
+            return Err(ProgramError::Custom(MerkleTreeError::TreeFull as u32));
+        }
+
+        let mut current = leaf;
+        let mut idx = self.next_index;
+        for level in 0..DEPTH {
+            if idx & 1 == 0 {
+                self.filled_subtrees[level] = current;
+                current = hash_pair(&current, &self.zeros[level]);
+            } else {
+                current = hash_pair(&self.filled_subtrees[level], &current);
+            }
+            idx >>= 1;
+        }
+
+        self.next_index += 1;
+        Ok(current)
+    }
+}
+
+#[repr(u32)]
+pub enum MerkleTreeError {
+    TreeFull = 0,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_tree() -> MerkleTree {
+        let mut tree = MerkleTree::zeroed();
+        tree.init();
+        tree
+    }
+
+    #[test]
+    fn zeros_chain_from_the_empty_leaf() {
+        let zeros = build_zeros();
+        assert_eq!(zeros[0], EMPTY_LEAF);
+        assert_eq!(zeros[1], hash_pair(&zeros[0], &zeros[0]));
+        assert_eq!(zeros[DEPTH - 1], hash_pair(&zeros[DEPTH - 2], &zeros[DEPTH - 2]));
+    }
+
+    #[test]
+    fn first_insert_combines_the_leaf_with_zeros_at_every_level() {
+        let mut tree = empty_tree();
+        let leaf = [7u8; 32];
+
+        let root = tree.insert(leaf).unwrap();
+
+        let mut expected = leaf;
+        for level in 0..DEPTH {
+            expected = hash_pair(&expected, &tree.zeros[level]);
+        }
+        assert_eq!(root, expected);
+        assert_eq!(tree.next_index, 1);
+    }
+
+    #[test]
+    fn second_insert_pairs_with_the_first_leaf() {
+        let mut tree = empty_tree();
+        let first = [1u8; 32];
+        let second = [2u8; 32];
+
+        tree.insert(first).unwrap();
+        let root = tree.insert(second).unwrap();
+
+        let mut expected = hash_pair(&first, &second);
+        for level in 1..DEPTH {
+            expected = hash_pair(&expected, &tree.zeros[level]);
+        }
+        assert_eq!(root, expected);
+        assert_eq!(tree.next_index, 2);
+    }
+
+    #[test]
+    fn insert_past_capacity_is_rejected() {
+        let mut tree = empty_tree();
+        tree.next_index = MAX_LEAVES;
+
+        assert!(tree.insert([0u8; 32]).is_err());
+    }
+}
@@ -0,0 +1,22 @@
+use solana_program_error::ProgramError;
+
+/// Withdrawal proof check for the (root, nullifier, recipient, amount) tuple
+/// being withdrawn. No verifying key or pairing check is wired in, so the
+/// proof is not verified and its public inputs are not bound: this rejects
+/// every withdrawal with `ProofError::VerifierNotImplemented`, whatever its
+/// arguments, rather than accepting an unverified proof (a no-op check would
+/// let anyone drain the vault with any nullifier and any retained root).
+pub fn verify_withdraw_proof(
+    _proof: &[u8],
+    _root: &[u8; 32],
+    _nullifier: &[u8; 32],
+    _recipient: &[u8; 32],
+    _amount: u64,
+) -> Result<(), ProgramError> {
+    Err(ProgramError::Custom(ProofError::VerifierNotImplemented as u32))
+}
+
+#[repr(u32)]
+pub enum ProofError {
+    VerifierNotImplemented = 0,
+}
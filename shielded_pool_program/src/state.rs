@@ -0,0 +1,87 @@
+use bytemuck::{Pod, Zeroable};
+use pinocchio::Address;
+
+use crate::merkle::MerkleTree;
+
+/// Number of recent roots retained so proofs built against a slightly stale
+/// tree still verify.
+pub const ROOT_HISTORY_SIZE: usize = 64;
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub struct ShieldedPoolState {
+    pub is_initialized: u8,
+    _padding: [u8; 7],
+    pub roots: [[u8; 32]; ROOT_HISTORY_SIZE],
+    pub root_cursor: u64,
+    pub tree: MerkleTree,
+    /// Lamports deposited and not yet withdrawn (deposits add, withdrawals
+    /// subtract, clawback resets it to 0), so the vault's actual balance can
+    /// be reconciled against what the tree claims was deposited.
+    pub total_deposited: u64,
+    /// Authority allowed to pause the pool and claw back the vault. Must be
+    /// set by the pool's initialize instruction; that instruction is not
+    /// part of this series, so a pool initialized before pause/clawback
+    /// existed has an all-zero authority here and neither instruction can be
+    /// used until the state account is migrated to carry a real authority.
+    pub authority: Address,
+    pub paused: u8,
+    _paused_padding: [u8; 7],
+}
+
+impl ShieldedPoolState {
+    pub const LEN: usize = core::mem::size_of::<Self>();
+
+    pub fn is_initialized(&self) -> bool {
+        self.is_initialized != 0
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused != 0
+    }
+
+    /// Pushes a newly derived root into the recent-roots ring buffer.
+    pub fn add_root(&mut self, root: [u8; 32]) {
+        let idx = (self.root_cursor as usize) % ROOT_HISTORY_SIZE;
+        self.roots[idx] = root;
+        self.root_cursor = self.root_cursor.wrapping_add(1);
+    }
+
+    /// Whether `root` is the current root or still within the retained
+    /// history window. Only scans the slots that have actually been
+    /// written, so an unfilled ring buffer (all-zero) doesn't make the
+    /// all-zero root spuriously "valid" before the pool has ever recorded
+    /// one.
+    pub fn root_valid(&self, root: &[u8; 32]) -> bool {
+        let populated = core::cmp::min(self.root_cursor as usize, ROOT_HISTORY_SIZE);
+        self.roots[..populated].iter().any(|candidate| candidate == root)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_root_evicts_the_oldest_entry_once_the_buffer_wraps() {
+        let mut state = ShieldedPoolState::zeroed();
+
+        for i in 0..=ROOT_HISTORY_SIZE {
+            state.add_root([i as u8; 32]);
+        }
+
+        assert!(!state.root_valid(&[0u8; 32]));
+        assert!(state.root_valid(&[ROOT_HISTORY_SIZE as u8; 32]));
+    }
+
+    #[test]
+    fn add_root_makes_the_root_valid_immediately() {
+        let mut state = ShieldedPoolState::zeroed();
+        let root = [9u8; 32];
+
+        state.add_root(root);
+
+        assert_eq!(state.root_cursor, 1);
+        assert!(state.root_valid(&root));
+    }
+}